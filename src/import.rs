@@ -0,0 +1,195 @@
+//! Loading player data from either a plain CSV export or Football Manager's
+//! native HTML "Views" export, detected from the file's extension/content.
+
+use std::error::Error;
+use std::fs;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+
+use crate::Player;
+
+/// The outcome of loading a player file: the rows that parsed cleanly, plus
+/// the rows that didn't (1-based position, error description). The position
+/// is the file line number for CSV input and the table row number, counting
+/// the header row as 1, for HTML input.
+pub struct LoadReport {
+    pub players: Vec<Player>,
+    pub failures: Vec<(u64, String)>,
+}
+
+/// Loads players from `path`, dispatching to the CSV or HTML importer based
+/// on its extension (falling back to sniffing the content, since FM's HTML
+/// views export is sometimes saved with a `.csv` or no extension at all).
+/// When `strict` is set, the first malformed row aborts the load instead of
+/// being skipped.
+pub fn load_players(path: &Path, strict: bool) -> Result<LoadReport, Box<dyn Error>> {
+    if is_html(path)? {
+        load_html(path, strict)
+    } else {
+        load_csv(path, strict)
+    }
+}
+
+fn is_html(path: &Path) -> Result<bool, Box<dyn Error>> {
+    if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+        if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") {
+            return Ok(true);
+        }
+    }
+    let mut head = Vec::new();
+    BufReader::new(fs::File::open(path)?).take(2048).read_to_end(&mut head)?;
+    let head = String::from_utf8_lossy(&head).to_lowercase();
+    Ok(head.contains("<table") || head.contains("<!doctype html"))
+}
+
+fn load_csv(path: &Path, strict: bool) -> Result<LoadReport, Box<dyn Error>> {
+    let file = fs::File::open(path)?;
+    let mut rdr = csv::Reader::from_reader(file);
+
+    let mut players = Vec::new();
+    let mut failures = Vec::new();
+    for result in rdr.deserialize::<Player>() {
+        match result {
+            Ok(player) => players.push(player),
+            Err(e) => {
+                let line = e.position().map_or(0, |pos| pos.line());
+                if strict {
+                    return Err(format!("aborting on malformed row at line {line}: {e}").into());
+                }
+                failures.push((line, e.to_string()));
+            }
+        }
+    }
+
+    Ok(LoadReport { players, failures })
+}
+
+/// Maps `Player` field names to the header text(s) FM uses for them in its
+/// HTML views, including the abbreviated column names FM falls back to when
+/// a view is too narrow to show full labels.
+const FIELD_ALIASES: &[(&str, &[&str])] = &[
+    ("name", &["name"]),
+    ("age", &["age"]),
+    ("club", &["club"]),
+    ("nationality", &["nat", "nationality"]),
+    ("position", &["position", "pos"]),
+    ("value", &["value", "val"]),
+    ("wage", &["wage"]),
+    ("current_ability", &["ca", "current ability"]),
+    ("potential_ability", &["pa", "potential ability"]),
+    ("finishing", &["finishing", "fin"]),
+    ("first_touch", &["first touch", "fir"]),
+    ("passing", &["passing", "pas"]),
+    ("technique", &["technique", "tec"]),
+    ("dribbling", &["dribbling", "dri"]),
+    ("tackling", &["tackling", "tck"]),
+    ("decisions", &["decisions", "dec"]),
+    ("anticipation", &["anticipation", "ant"]),
+    ("composure", &["composure", "cmp"]),
+    ("vision", &["vision", "vis"]),
+    ("work_rate", &["work rate", "wor"]),
+    ("acceleration", &["acceleration", "acc"]),
+    ("pace", &["pace", "pac"]),
+    ("stamina", &["stamina", "sta"]),
+    ("strength", &["strength", "str"]),
+    ("jumping", &["jumping", "jum"]),
+];
+
+fn resolve_column(header: &str) -> Option<&'static str> {
+    let key = header.trim().to_lowercase();
+    FIELD_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&key.as_str()))
+        .map(|(field, _)| *field)
+}
+
+fn load_html(path: &Path, strict: bool) -> Result<LoadReport, Box<dyn Error>> {
+    let text = fs::read_to_string(path)?;
+    let document = Html::parse_document(&text);
+
+    let table_sel = Selector::parse("table").unwrap();
+    let row_sel = Selector::parse("tr").unwrap();
+    let header_cell_sel = Selector::parse("th").unwrap();
+    let cell_sel = Selector::parse("td").unwrap();
+
+    let table = document
+        .select(&table_sel)
+        .next()
+        .ok_or("no <table> found in HTML export")?;
+
+    let mut rows = table.select(&row_sel);
+    let header_row = rows.next().ok_or("HTML table has no header row")?;
+    let columns: Vec<Option<&'static str>> = header_row
+        .select(&header_cell_sel)
+        .map(|th| resolve_column(&th.text().collect::<String>()))
+        .collect();
+
+    let mut players = Vec::new();
+    let mut failures = Vec::new();
+    for (i, row) in rows.enumerate() {
+        // +2: the header is row 1, and `i` is 0-based over the rows after it.
+        let row_number = i as u64 + 2;
+        match parse_row(&row, &columns, &cell_sel) {
+            Ok(Some(player)) => players.push(player),
+            Ok(None) => {}
+            Err(e) => {
+                if strict {
+                    return Err(format!("aborting on malformed row at table row {row_number}: {e}").into());
+                }
+                failures.push((row_number, e));
+            }
+        }
+    }
+
+    Ok(LoadReport { players, failures })
+}
+
+fn parse_row(
+    row: &ElementRef,
+    columns: &[Option<&'static str>],
+    cell_sel: &Selector,
+) -> Result<Option<Player>, String> {
+    let cells: Vec<String> = row
+        .select(cell_sel)
+        .map(|td| td.text().collect::<String>().trim().to_string())
+        .collect();
+    if cells.is_empty() {
+        return Ok(None);
+    }
+
+    let mut fields = serde_json::Map::new();
+    for (column, text) in columns.iter().zip(cells.iter()) {
+        if let Some(field) = column {
+            fields.insert((*field).to_string(), cell_to_value(field, text)?);
+        }
+    }
+
+    serde_json::from_value(Value::Object(fields)).map(Some).map_err(|e| e.to_string())
+}
+
+fn cell_to_value(field: &str, text: &str) -> Result<Value, String> {
+    match field {
+        "name" | "club" | "nationality" | "position" => Ok(Value::String(text.to_string())),
+        "value" | "wage" => Ok(Value::from(parse_money(text)?)),
+        _ => Ok(Value::from(parse_number(text)?.round() as u64)),
+    }
+}
+
+fn parse_money(text: &str) -> Result<f64, String> {
+    let multiplier = if text.to_lowercase().contains('m') {
+        1_000_000.0
+    } else if text.to_lowercase().contains('k') {
+        1_000.0
+    } else {
+        1.0
+    };
+    parse_number(text).map(|n| n * multiplier)
+}
+
+fn parse_number(text: &str) -> Result<f64, String> {
+    let cleaned: String = text.chars().filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-').collect();
+    cleaned.parse::<f64>().map_err(|_| format!("cannot parse number from {text:?}"))
+}
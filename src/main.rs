@@ -1,11 +1,18 @@
+mod import;
+mod output;
+mod query;
+mod roles;
+
 use std::error::Error;
-use std::fs::File;
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use rayon::prelude::*;
 use clap::Parser;
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
+use output::OutputFormat;
+use query::Predicate;
+use roles::Role;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 struct Player {
@@ -43,62 +50,52 @@ struct Player {
 #[command(author, version, about, long_about = None)]
 struct Args {
     
+    /// Player export to load: a CSV file, or a Football Manager "Views" HTML export.
     #[arg(short, long)]
     file: PathBuf,
 
-    #[arg(short, long, default_value = "23")]
+    #[arg(short = 'a', long, default_value = "23")]
     max_age: u8,
 
-    #[arg(short, long, default_value = "5.0")]
+    #[arg(short = 'v', long, default_value = "5.0")]
     max_value: f64,
-    
-    #[arg(short, long, default_value = "130")]
+
+    #[arg(short = 'i', long, default_value = "130")]
     min_potential: u8,
 
     #[arg(short, long)]
     position: String,
+
+    /// Abort on the first malformed row (CSV or HTML) instead of skipping it.
+    #[arg(long)]
+    strict: bool,
+
+    /// Free-form boolean expression over player fields, e.g.
+    /// "finishing >= 15 AND age < 21 AND (value < 2.5 OR potential_ability > 150)".
+    /// When set, this replaces the max-age/max-value/min-potential/position filters;
+    /// `--position` still selects the role used for scoring and display.
+    #[arg(short = 'q', long)]
+    query: Option<String>,
+
+    /// Output format for the recommendation list.
+    #[arg(long, value_enum, default_value = "pretty")]
+    format: OutputFormat,
+
+    /// TOML file defining scouting roles (weights + displayed attributes).
+    /// Defaults to the built-in ST/CM/CB roles when omitted.
+    #[arg(long)]
+    roles: Option<PathBuf>,
 }
 
 impl Player {
-    fn calculate_score(&self, position: &str) -> f64 {
-        let position_weights = match position.to_uppercase().as_str() {
-            "ST" => vec![
-                (self.finishing, 2.0),
-                (self.first_touch, 1.5),
-                (self.acceleration, 1.5),
-                (self.pace, 1.5),
-                (self.composure, 1.0),
-            ],
-            "CM" => vec![
-                (self.passing, 2.0),
-                (self.vision, 1.5),
-                (self.decisions, 1.5),
-                (self.stamina, 1.0),
-                (self.work_rate, 1.5),
-            ],
-            "CB" => vec![
-                (self.tackling, 2.0),
-                (self.strength, 1.5),
-                (self.jumping, 1.5),
-                (self.anticipation, 1.5),
-                (self.decisions, 1.0),
-            ],
-            _ => vec![
-                (self.technique, 1.0),
-                (self.decisions, 1.0),
-                (self.stamina, 1.0),
-                (self.strength, 1.0),
-                (self.work_rate, 1.0),
-            ],
-        };
-
-        let attribute_score: f64 = position_weights.iter()
-            .map(|(attr, weight)| *attr as f64 * weight)
-            .sum::<f64>() / position_weights.iter().map(|(_, w)| w).sum::<f64>();
+    fn calculate_score(&self, role: &Role) -> f64 {
+        let attribute_score: f64 = role.weights.iter()
+            .map(|(field, weight)| field.value(self) * weight)
+            .sum::<f64>() / role.weights.iter().map(|(_, w)| w).sum::<f64>();
 
         let potential_score = self.potential_ability as f64 / 200.0;
         let value_score = 1.0 - (self.value.min(50_000_000.0) / 50_000_000.0);
-        
+
         (attribute_score * 0.4) + (potential_score * 0.4) + (value_score * 0.2)
     }
 }
@@ -106,8 +103,14 @@ impl Player {
 fn find_gems(
     players: &[Player],
     args: &Args,
+    query: Option<&Predicate>,
+    role: &Role,
 ) -> Vec<Player> {
-    let pb = ProgressBar::new(players.len() as u64);
+    let pb = if args.format.is_pretty() {
+        ProgressBar::new(players.len() as u64)
+    } else {
+        ProgressBar::hidden()
+    };
     pb.set_style(ProgressStyle::default_bar()
         .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
         .unwrap());
@@ -115,10 +118,15 @@ fn find_gems(
     let mut filtered: Vec<_> = players.par_iter()
         .filter(|p| {
             pb.inc(1);
-            p.age <= args.max_age &&
-            p.value <= args.max_value * 1_000_000.0 &&
-            p.potential_ability >= args.min_potential &&
-            p.position.to_lowercase().contains(&args.position.to_lowercase())
+            match query {
+                Some(predicate) => predicate.eval(p),
+                None => {
+                    p.age <= args.max_age &&
+                    p.value <= args.max_value * 1_000_000.0 &&
+                    p.potential_ability >= args.min_potential &&
+                    p.position.to_lowercase().contains(&args.position.to_lowercase())
+                }
+            }
         })
         .cloned()
         .collect();
@@ -126,82 +134,84 @@ fn find_gems(
     pb.finish_with_message("Analysis complete");
 
     filtered.sort_by(|a, b| {
-        let score_a = a.calculate_score(&args.position);
-        let score_b = b.calculate_score(&args.position);
+        let score_a = a.calculate_score(role);
+        let score_b = b.calculate_score(role);
         score_b.partial_cmp(&score_a).unwrap()
     });
 
     filtered
 }
 
-fn display_player(player: &Player, position: &str) {
+fn display_player(player: &Player, role: &Role) {
     println!("\n{}", "=".repeat(50).yellow());
     println!("{}", player.name.bright_green().bold());
     println!("{}", "=".repeat(50).yellow());
-    
+
     println!("Club: {}", player.club.cyan());
     println!("Age: {}", player.age.to_string().cyan());
     println!("Value: €{:.2}M", player.value / 1_000_000.0);
     println!("Wage: €{:.2}K/week", player.wage / 1_000.0);
     println!("Current Ability: {}", player.current_ability.to_string().yellow());
     println!("Potential Ability: {}", player.potential_ability.to_string().bright_yellow());
-    
+
     println!("\n{}", "Key Attributes:".underline());
-    match position.to_uppercase().as_str() {
-        "ST" => {
-            println!("Finishing: {}", player.finishing);
-            println!("First Touch: {}", player.first_touch);
-            println!("Acceleration: {}", player.acceleration);
-            println!("Pace: {}", player.pace);
-            println!("Composure: {}", player.composure);
-        },
-        "CM" => {
-            println!("Passing: {}", player.passing);
-            println!("Vision: {}", player.vision);
-            println!("Decisions: {}", player.decisions);
-            println!("Stamina: {}", player.stamina);
-            println!("Work Rate: {}", player.work_rate);
-        },
-        "CB" => {
-            println!("Tackling: {}", player.tackling);
-            println!("Strength: {}", player.strength);
-            println!("Jumping: {}", player.jumping);
-            println!("Anticipation: {}", player.anticipation);
-            println!("Decisions: {}", player.decisions);
-        },
-        _ => {
-            println!("Technique: {}", player.technique);
-            println!("Decisions: {}", player.decisions);
-            println!("Work Rate: {}", player.work_rate);
-            println!("Stamina: {}", player.stamina);
-        }
+    for field in &role.display {
+        println!("{}: {}", field.label(), field.value(player));
     }
-    
-    println!("\nOverall Score: {:.2}", player.calculate_score(position));
+
+    println!("\nOverall Score: {:.2}", player.calculate_score(role));
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args = Args::parse();
 
-    println!("{}", "\nFM24 Scout - Hidden Gems Finder".bright_blue().bold());
-    println!("{}", "=".repeat(50).blue());
+    if args.format.is_pretty() {
+        println!("{}", "\nFM24 Scout - Hidden Gems Finder".bright_blue().bold());
+        println!("{}", "=".repeat(50).blue());
+        println!("Loading and analyzing player data...");
+    }
 
-    let file = File::open(&args.file)?;
-    let mut rdr = csv::Reader::from_reader(file);
-    
-    println!("Loading and analyzing player data...");
-    
-    let players: Vec<Player> = rdr.deserialize()
-        .filter_map(Result::ok)
-        .collect();
+    let import::LoadReport { players, failures } = import::load_players(&args.file, args.strict)?;
+
+    if let Some((first_row, first_err)) = failures.first() {
+        eprintln!(
+            "{}",
+            format!(
+                "skipped {} of {} rows — first error at line {}: {}",
+                failures.len(),
+                players.len() + failures.len(),
+                first_row,
+                first_err
+            )
+            .yellow()
+        );
+    }
+
+    let predicate = args.query.as_deref().map(query::parse_query).transpose()?;
 
-    let gems = find_gems(&players, &args);
+    let role_set = roles::load_roles(args.roles.as_deref())?;
+    let role = roles::lookup(&role_set, &args.position);
 
-    println!("\nFound {} potential signings:", gems.len());
+    let gems = find_gems(&players, &args, predicate.as_ref(), role);
+    let total_matches = gems.len();
+    let top: Vec<Player> = gems.into_iter().take(10).collect();
 
-    for (i, player) in gems.iter().take(10).enumerate() {
-        println!("\n{}. {}", i + 1, "Recommendation".bright_purple());
-        display_player(player, &args.position);
+    match args.format {
+        OutputFormat::Pretty => {
+            println!("\nFound {} potential signings:", total_matches);
+            for (i, player) in top.iter().enumerate() {
+                println!("\n{}. {}", i + 1, "Recommendation".bright_purple());
+                display_player(player, role);
+            }
+        }
+        OutputFormat::Json => {
+            eprintln!("{}", format!("{total_matches} total matches, showing top {}", top.len()).yellow());
+            output::write_json(&top, role)?
+        }
+        OutputFormat::Csv => {
+            eprintln!("{}", format!("{total_matches} total matches, showing top {}", top.len()).yellow());
+            output::write_csv(&top, role)?
+        }
     }
 
     Ok(())
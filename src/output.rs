@@ -0,0 +1,131 @@
+//! Rendering the final recommendation list in different output formats.
+
+use std::error::Error;
+use std::io;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::roles::Role;
+use crate::Player;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Pretty,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    /// Whether this format wants the progress bar and banner, or a clean stream.
+    pub fn is_pretty(self) -> bool {
+        matches!(self, OutputFormat::Pretty)
+    }
+}
+
+/// A recommended player annotated with its rank and computed score, for
+/// machine-readable output formats.
+#[derive(Serialize)]
+pub struct ScoredPlayer {
+    #[serde(flatten)]
+    pub player: Player,
+    pub score: f64,
+    pub rank: usize,
+}
+
+fn scored_players(gems: &[Player], role: &Role) -> Vec<ScoredPlayer> {
+    gems.iter()
+        .enumerate()
+        .map(|(i, player)| ScoredPlayer {
+            player: player.clone(),
+            score: player.calculate_score(role),
+            rank: i + 1,
+        })
+        .collect()
+}
+
+pub fn write_json(gems: &[Player], role: &Role) -> Result<(), Box<dyn Error>> {
+    let scored = scored_players(gems, role);
+    serde_json::to_writer_pretty(io::stdout(), &scored)?;
+    println!();
+    Ok(())
+}
+
+/// A flat, CSV-friendly copy of `ScoredPlayer`: the `csv` crate can't
+/// serialize `#[serde(flatten)]`'d fields, so this spells out `Player`'s
+/// fields alongside `score`/`rank` instead of nesting them.
+#[derive(Serialize)]
+struct ScoredPlayerRow {
+    name: String,
+    age: u8,
+    club: String,
+    nationality: String,
+    position: String,
+    value: f64,
+    wage: f64,
+    current_ability: u8,
+    potential_ability: u8,
+    finishing: u8,
+    first_touch: u8,
+    passing: u8,
+    technique: u8,
+    dribbling: u8,
+    tackling: u8,
+    decisions: u8,
+    anticipation: u8,
+    composure: u8,
+    vision: u8,
+    work_rate: u8,
+    acceleration: u8,
+    pace: u8,
+    stamina: u8,
+    strength: u8,
+    jumping: u8,
+    score: f64,
+    rank: usize,
+}
+
+impl From<&ScoredPlayer> for ScoredPlayerRow {
+    fn from(scored: &ScoredPlayer) -> Self {
+        let p = &scored.player;
+        ScoredPlayerRow {
+            name: p.name.clone(),
+            age: p.age,
+            club: p.club.clone(),
+            nationality: p.nationality.clone(),
+            position: p.position.clone(),
+            value: p.value,
+            wage: p.wage,
+            current_ability: p.current_ability,
+            potential_ability: p.potential_ability,
+            finishing: p.finishing,
+            first_touch: p.first_touch,
+            passing: p.passing,
+            technique: p.technique,
+            dribbling: p.dribbling,
+            tackling: p.tackling,
+            decisions: p.decisions,
+            anticipation: p.anticipation,
+            composure: p.composure,
+            vision: p.vision,
+            work_rate: p.work_rate,
+            acceleration: p.acceleration,
+            pace: p.pace,
+            stamina: p.stamina,
+            strength: p.strength,
+            jumping: p.jumping,
+            score: scored.score,
+            rank: scored.rank,
+        }
+    }
+}
+
+pub fn write_csv(gems: &[Player], role: &Role) -> Result<(), Box<dyn Error>> {
+    let scored = scored_players(gems, role);
+    let mut wtr = csv::Writer::from_writer(io::stdout());
+    for row in &scored {
+        wtr.serialize(ScoredPlayerRow::from(row))?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
@@ -0,0 +1,251 @@
+//! A small boolean query language for filtering players on arbitrary
+//! attributes, e.g. `finishing >= 15 AND age < 21 AND (value < 2.5 OR potential_ability > 150)`.
+//!
+//! Grammar (loosest-binding first):
+//!   expr       -> term (OR term)*
+//!   term       -> factor (AND factor)*
+//!   factor     -> comparison | '(' expr ')'
+//!   comparison -> field op number
+//!   field      -> identifier matching a numeric `Player` field
+//!   op         -> '>=' | '<=' | '==' | '!=' | '>' | '<'
+
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{char, digit1, multispace0};
+use nom::combinator::{map, map_res, opt, recognize};
+use nom::multi::fold_many0;
+use nom::sequence::{delimited, preceded, tuple};
+use nom::IResult;
+
+use crate::Player;
+
+/// A numeric `Player` field that can appear on the left-hand side of a comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Field {
+    Age,
+    Value,
+    Wage,
+    CurrentAbility,
+    PotentialAbility,
+    Finishing,
+    FirstTouch,
+    Passing,
+    Technique,
+    Dribbling,
+    Tackling,
+    Decisions,
+    Anticipation,
+    Composure,
+    Vision,
+    WorkRate,
+    Acceleration,
+    Pace,
+    Stamina,
+    Strength,
+    Jumping,
+}
+
+impl Field {
+    pub(crate) fn from_name(name: &str) -> Option<Field> {
+        Some(match name {
+            "age" => Field::Age,
+            "value" => Field::Value,
+            "wage" => Field::Wage,
+            "current_ability" => Field::CurrentAbility,
+            "potential_ability" => Field::PotentialAbility,
+            "finishing" => Field::Finishing,
+            "first_touch" => Field::FirstTouch,
+            "passing" => Field::Passing,
+            "technique" => Field::Technique,
+            "dribbling" => Field::Dribbling,
+            "tackling" => Field::Tackling,
+            "decisions" => Field::Decisions,
+            "anticipation" => Field::Anticipation,
+            "composure" => Field::Composure,
+            "vision" => Field::Vision,
+            "work_rate" => Field::WorkRate,
+            "acceleration" => Field::Acceleration,
+            "pace" => Field::Pace,
+            "stamina" => Field::Stamina,
+            "strength" => Field::Strength,
+            "jumping" => Field::Jumping,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn value(self, player: &Player) -> f64 {
+        match self {
+            Field::Age => player.age as f64,
+            // In millions, like `--max-value`, so `value < 2.5` means "under €2.5M".
+            Field::Value => player.value / 1_000_000.0,
+            Field::Wage => player.wage,
+            Field::CurrentAbility => player.current_ability as f64,
+            Field::PotentialAbility => player.potential_ability as f64,
+            Field::Finishing => player.finishing as f64,
+            Field::FirstTouch => player.first_touch as f64,
+            Field::Passing => player.passing as f64,
+            Field::Technique => player.technique as f64,
+            Field::Dribbling => player.dribbling as f64,
+            Field::Tackling => player.tackling as f64,
+            Field::Decisions => player.decisions as f64,
+            Field::Anticipation => player.anticipation as f64,
+            Field::Composure => player.composure as f64,
+            Field::Vision => player.vision as f64,
+            Field::WorkRate => player.work_rate as f64,
+            Field::Acceleration => player.acceleration as f64,
+            Field::Pace => player.pace as f64,
+            Field::Stamina => player.stamina as f64,
+            Field::Strength => player.strength as f64,
+            Field::Jumping => player.jumping as f64,
+        }
+    }
+
+    /// The human-readable label used when displaying this attribute, e.g. "First Touch".
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Field::Age => "Age",
+            Field::Value => "Value",
+            Field::Wage => "Wage",
+            Field::CurrentAbility => "Current Ability",
+            Field::PotentialAbility => "Potential Ability",
+            Field::Finishing => "Finishing",
+            Field::FirstTouch => "First Touch",
+            Field::Passing => "Passing",
+            Field::Technique => "Technique",
+            Field::Dribbling => "Dribbling",
+            Field::Tackling => "Tackling",
+            Field::Decisions => "Decisions",
+            Field::Anticipation => "Anticipation",
+            Field::Composure => "Composure",
+            Field::Vision => "Vision",
+            Field::WorkRate => "Work Rate",
+            Field::Acceleration => "Acceleration",
+            Field::Pace => "Pace",
+            Field::Stamina => "Stamina",
+            Field::Strength => "Strength",
+            Field::Jumping => "Jumping",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+/// A parsed `--query` expression, ready to be evaluated against a `Player`.
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    Cmp(Field, Op, f64),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+}
+
+impl Predicate {
+    pub fn eval(&self, player: &Player) -> bool {
+        match self {
+            Predicate::Cmp(field, op, rhs) => compare(field.value(player), *op, *rhs),
+            Predicate::And(a, b) => a.eval(player) && b.eval(player),
+            Predicate::Or(a, b) => a.eval(player) || b.eval(player),
+        }
+    }
+}
+
+fn compare(lhs: f64, op: Op, rhs: f64) -> bool {
+    match op {
+        Op::Gt => lhs > rhs,
+        Op::Ge => lhs >= rhs,
+        Op::Lt => lhs < rhs,
+        Op::Le => lhs <= rhs,
+        Op::Eq => (lhs - rhs).abs() < f64::EPSILON,
+        Op::Ne => (lhs - rhs).abs() >= f64::EPSILON,
+    }
+}
+
+fn ws<'a, F, O>(mut inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    move |input: &'a str| {
+        let (input, _) = multispace0(input)?;
+        let (input, out) = inner(input)?;
+        let (input, _) = multispace0(input)?;
+        Ok((input, out))
+    }
+}
+
+fn parse_identifier(input: &str) -> IResult<&str, &str> {
+    recognize(take_while1(|c: char| c.is_alphanumeric() || c == '_'))(input)
+}
+
+fn parse_field(input: &str) -> IResult<&str, Field> {
+    map_res(parse_identifier, |name| Field::from_name(name).ok_or(()))(input)
+}
+
+fn parse_op(input: &str) -> IResult<&str, Op> {
+    alt((
+        map(tag(">="), |_| Op::Ge),
+        map(tag("<="), |_| Op::Le),
+        map(tag("=="), |_| Op::Eq),
+        map(tag("!="), |_| Op::Ne),
+        map(tag(">"), |_| Op::Gt),
+        map(tag("<"), |_| Op::Lt),
+    ))(input)
+}
+
+fn parse_number(input: &str) -> IResult<&str, f64> {
+    map_res(
+        recognize(tuple((
+            opt(char('-')),
+            digit1,
+            opt(tuple((char('.'), digit1))),
+        ))),
+        |s: &str| s.parse::<f64>(),
+    )(input)
+}
+
+fn parse_comparison(input: &str) -> IResult<&str, Predicate> {
+    map(
+        tuple((ws(parse_field), ws(parse_op), ws(parse_number))),
+        |(field, op, value)| Predicate::Cmp(field, op, value),
+    )(input)
+}
+
+fn parse_factor(input: &str) -> IResult<&str, Predicate> {
+    alt((
+        delimited(ws(char('(')), parse_expr, ws(char(')'))),
+        parse_comparison,
+    ))(input)
+}
+
+fn parse_term(input: &str) -> IResult<&str, Predicate> {
+    let (input, init) = parse_factor(input)?;
+    fold_many0(
+        preceded(ws(tag("AND")), parse_factor),
+        move || init.clone(),
+        |acc, factor| Predicate::And(Box::new(acc), Box::new(factor)),
+    )(input)
+}
+
+fn parse_expr(input: &str) -> IResult<&str, Predicate> {
+    let (input, init) = parse_term(input)?;
+    fold_many0(
+        preceded(ws(tag("OR")), parse_term),
+        move || init.clone(),
+        |acc, term| Predicate::Or(Box::new(acc), Box::new(term)),
+    )(input)
+}
+
+/// Parses a complete `--query` expression, rejecting any trailing input.
+pub fn parse_query(input: &str) -> Result<Predicate, String> {
+    match ws(parse_expr)(input) {
+        Ok(("", predicate)) => Ok(predicate),
+        Ok((remaining, _)) => Err(format!("unexpected trailing input in query: {remaining:?}")),
+        Err(e) => Err(format!("failed to parse query {input:?}: {e}")),
+    }
+}
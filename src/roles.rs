@@ -0,0 +1,116 @@
+//! Scouting role definitions: which attributes to weight in `calculate_score`
+//! and which ones to show in `display_player`, loadable from a `--roles` TOML
+//! file so users aren't limited to the built-in ST/CM/CB roles.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::query::Field;
+
+/// Weights and display attributes for a single scouting role.
+pub struct Role {
+    pub weights: Vec<(Field, f64)>,
+    pub display: Vec<Field>,
+}
+
+pub type RoleSet = HashMap<String, Role>;
+
+/// Raw shape of a role as written in the TOML file, before attribute names
+/// are resolved against `Player`'s fields.
+#[derive(Deserialize)]
+struct RawRole {
+    weights: Vec<(String, f64)>,
+    #[serde(default)]
+    display: Vec<String>,
+}
+
+/// The role looked up for a position that isn't covered by the role set.
+const FALLBACK_ROLE: &str = "DEFAULT";
+
+/// Loads role definitions from `path`, or the built-in ST/CM/CB/DEFAULT
+/// roles when no file is given, preserving the tool's existing behavior.
+pub fn load_roles(path: Option<&Path>) -> Result<RoleSet, Box<dyn Error>> {
+    match path {
+        Some(path) => {
+            let text = fs::read_to_string(path)?;
+            let raw: HashMap<String, RawRole> = toml::from_str(&text)?;
+            let mut roles: RoleSet = raw
+                .into_iter()
+                .map(|(name, role)| Ok((name.to_uppercase(), resolve(role)?)))
+                .collect::<Result<_, Box<dyn Error>>>()?;
+            roles.entry(FALLBACK_ROLE.to_string()).or_insert_with(|| default_roles().remove(FALLBACK_ROLE).unwrap());
+            Ok(roles)
+        }
+        None => Ok(default_roles()),
+    }
+}
+
+fn resolve(raw: RawRole) -> Result<Role, Box<dyn Error>> {
+    let weights = raw
+        .weights
+        .into_iter()
+        .map(|(name, weight)| resolve_field(&name).map(|field| (field, weight)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if weights.iter().map(|(_, w)| w).sum::<f64>() == 0.0 {
+        return Err("role weights must be non-empty and sum to a nonzero value".into());
+    }
+
+    let display = if raw.display.is_empty() {
+        weights.iter().map(|(field, _)| *field).collect()
+    } else {
+        raw.display.iter().map(|name| resolve_field(name)).collect::<Result<Vec<_>, _>>()?
+    };
+
+    Ok(Role { weights, display })
+}
+
+fn resolve_field(name: &str) -> Result<Field, Box<dyn Error>> {
+    Field::from_name(name).ok_or_else(|| format!("unknown attribute `{name}` in roles file").into())
+}
+
+fn default_roles() -> RoleSet {
+    use Field::*;
+    let mut roles = RoleSet::new();
+    roles.insert(
+        "ST".to_string(),
+        Role {
+            weights: vec![(Finishing, 2.0), (FirstTouch, 1.5), (Acceleration, 1.5), (Pace, 1.5), (Composure, 1.0)],
+            display: vec![Finishing, FirstTouch, Acceleration, Pace, Composure],
+        },
+    );
+    roles.insert(
+        "CM".to_string(),
+        Role {
+            weights: vec![(Passing, 2.0), (Vision, 1.5), (Decisions, 1.5), (Stamina, 1.0), (WorkRate, 1.5)],
+            display: vec![Passing, Vision, Decisions, Stamina, WorkRate],
+        },
+    );
+    roles.insert(
+        "CB".to_string(),
+        Role {
+            weights: vec![(Tackling, 2.0), (Strength, 1.5), (Jumping, 1.5), (Anticipation, 1.5), (Decisions, 1.0)],
+            display: vec![Tackling, Strength, Jumping, Anticipation, Decisions],
+        },
+    );
+    roles.insert(
+        FALLBACK_ROLE.to_string(),
+        Role {
+            weights: vec![(Technique, 1.0), (Decisions, 1.0), (Stamina, 1.0), (Strength, 1.0), (WorkRate, 1.0)],
+            display: vec![Technique, Decisions, WorkRate, Stamina],
+        },
+    );
+    roles
+}
+
+/// Looks up a role by name (case-insensitive), falling back to `DEFAULT`.
+pub fn lookup<'a>(roles: &'a RoleSet, name: &str) -> &'a Role {
+    roles
+        .get(&name.to_uppercase())
+        .or_else(|| roles.get(FALLBACK_ROLE))
+        .expect("role set must define a DEFAULT role")
+}